@@ -1,3 +1,5 @@
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -5,6 +7,15 @@ pub enum Error {
     #[error("client error: '{0}'")]
     ClientError(String),
 
+    #[error("API error ({status}): {message}")]
+    ApiError {
+        status: u16,
+        code: Option<String>,
+        message: String,
+        type_: Option<String>,
+        link: Option<String>,
+    },
+
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
 
@@ -16,6 +27,92 @@ pub enum Error {
 
     #[error(transparent)]
     Oauth2ExecuteError(#[from] oauth2::ExecuteError),
+
+    #[error("JSON-RPC error {code}: {message}")]
+    JsonRpcError { code: i64, message: String },
+
+    #[error("ID token verification failed: {0}")]
+    IdTokenError(String),
+
+    #[error("session error: '{0}'")]
+    SessionError(String),
+
+    #[error("retries exhausted after {attempts} attempt(s): {last}")]
+    RetriesExhausted { attempts: u32, last: Box<Error> },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Implemented by the JSON error envelope a particular API returns on non-2xx responses.
+///
+/// `ApiClient::new_with_error_body` is generic over this trait so each API's own error shape (Meilisearch-style
+/// `{ "message", "code", "type", "link" }`, or something else entirely) can be decoded and
+/// mapped to [`Error::ApiError`].
+pub trait ApiErrorBody: DeserializeOwned + Debug {
+    fn into_error(self, status: reqwest::StatusCode) -> Error;
+}
+
+/// The JSON error envelope used by services like Meilisearch:
+/// `{ "message": "...", "code": "invalid_api_key", "type": "auth", "link": "..." }`.
+#[derive(Debug, serde::Deserialize)]
+pub struct DefaultApiErrorBody {
+    pub message: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default, rename = "type")]
+    pub type_: Option<String>,
+    #[serde(default)]
+    pub link: Option<String>,
+}
+
+impl ApiErrorBody for DefaultApiErrorBody {
+    fn into_error(self, status: reqwest::StatusCode) -> Error {
+        Error::ApiError {
+            status: status.as_u16(),
+            code: self.code,
+            message: self.message,
+            type_: self.type_,
+            link: self.link,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_matching_error_envelope() {
+        let text = r#"{"message":"invalid API key","code":"invalid_api_key","type":"auth","link":"https://docs.example.com/errors#invalid_api_key"}"#;
+        let body: DefaultApiErrorBody = serde_json::from_str(text).unwrap();
+        let err = body.into_error(reqwest::StatusCode::UNAUTHORIZED);
+
+        match err {
+            Error::ApiError { status, code, message, type_, link } => {
+                assert_eq!(status, 401);
+                assert_eq!(code.as_deref(), Some("invalid_api_key"));
+                assert_eq!(message, "invalid API key");
+                assert_eq!(type_.as_deref(), Some("auth"));
+                assert_eq!(link.as_deref(), Some("https://docs.example.com/errors#invalid_api_key"));
+            },
+            other => panic!("expected Error::ApiError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_envelope_with_only_required_fields() {
+        let body: DefaultApiErrorBody = serde_json::from_str(r#"{"message":"boom"}"#).unwrap();
+        assert_eq!(body.code, None);
+        assert_eq!(body.type_, None);
+        assert_eq!(body.link, None);
+    }
+
+    #[test]
+    fn falls_back_to_raw_text_when_body_does_not_match_envelope() {
+        // Not JSON at all, so this doesn't parse as `DefaultApiErrorBody` - callers fall
+        // back to a raw-text `Error::ApiError` (see `ApiClient::parse_response`).
+        let text = "<html>502 Bad Gateway</html>";
+        let decoded: Option<DefaultApiErrorBody> = serde_json::from_str(text).ok();
+        assert!(decoded.is_none());
+    }
+}
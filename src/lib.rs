@@ -2,14 +2,23 @@
 extern crate log;
 
 pub mod error;
+pub mod oidc;
+pub mod retry;
+pub mod token_store;
 pub mod types;
 
 use reqwest::header::*;
 use reqwest::Method;
 use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use error::*;
+pub use error::{ApiErrorBody, DefaultApiErrorBody};
 pub use oauth2::{AccessToken, AuthType, AuthorizationCode, RefreshToken, StandardToken, Token};
+pub use oidc::{IdTokenClaims, OidcConfig};
+pub use retry::RetryPolicy;
+pub use token_store::{InMemoryTokenStore, TokenStore};
 use std::fmt::Debug;
 use url::Url;
 
@@ -34,30 +43,95 @@ pub enum AuthConfig {
     NoAuth,
     AuthorizationHeader(AuthorizationHeaderConfig),
     OAuth2(OAuth2Config),
+    Oidc(OidcConfig),
+    /// For APIs that authenticate via a login POST returning a `Set-Cookie` session token
+    /// (common in JSON-RPC/portal-style APIs). See `ApiClient::login`.
+    CookieSession,
 }
 
 pub trait JsonResponse: DeserializeOwned + Debug {}
 
 impl<T> JsonResponse for T where T: DeserializeOwned + Debug {}
 
+/// Decodes the body of a non-2xx response into an [`Error::ApiError`]. Swap this out via
+/// [`ApiClient::new_with_error_body`] to match a particular API's error envelope.
+type ErrorDecoder = dyn Fn(reqwest::StatusCode, &str) -> Option<Error> + Send + Sync;
+
+fn error_decoder_for<B: ApiErrorBody + 'static>() -> Arc<ErrorDecoder> {
+    Arc::new(|status, text| serde_json::from_str::<B>(text).ok().map(|body| body.into_error(status)))
+}
+
 pub struct ApiClient {
     client: reqwest::Client,
     oauth_client: Option<oauth2::Client>,
+    oidc_config: Option<OidcConfig>,
+    oidc_state: tokio::sync::OnceCell<oidc::OidcState>,
     base_url: reqwest::Url,
+    next_rpc_id: AtomicU64,
+    token_store: Arc<dyn TokenStore + Send + Sync>,
+    refresh_lock: tokio::sync::Mutex<()>,
+    cookie_jar: Option<Arc<reqwest_cookie_store::CookieStoreMutex>>,
+    retry_policy: RetryPolicy,
+    error_decoder: Arc<ErrorDecoder>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct JsonRpcEnvelope<T> {
+    // The JSON-RPC 2.0 spec allows string ids and requires `null` on parse-error /
+    // invalid-request responses, so this can't be a plain `u64`.
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    #[serde(default)]
+    result: Option<T>,
+    #[serde(default)]
+    error: Option<JsonRpcErrorBody>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct JsonRpcErrorBody {
+    code: i64,
+    message: String,
 }
 
 impl ApiClient {
-    pub fn new(api_url: &str, auth: AuthConfig, default_headers: Option<HeaderMap>) -> Result<ApiClient> {
+    /// `token_store` backs automatic OAuth2/OIDC token refresh (see `handle_request`); pass
+    /// `None` to use the default [`InMemoryTokenStore`]. `retry_policy` controls retries of
+    /// transient failures; pass [`RetryPolicy::none`] (the `Default`) to retry nothing.
+    ///
+    /// Non-2xx response bodies are decoded using [`DefaultApiErrorBody`] (the Meilisearch-style
+    /// envelope); use [`Self::new_with_error_body`] if the API uses a different error shape.
+    pub fn new(
+        api_url: &str,
+        auth: AuthConfig,
+        default_headers: Option<HeaderMap>,
+        token_store: Option<Arc<dyn TokenStore + Send + Sync>>,
+        retry_policy: RetryPolicy,
+    ) -> Result<ApiClient> {
+        Self::new_with_error_body::<DefaultApiErrorBody>(api_url, auth, default_headers, token_store, retry_policy)
+    }
+
+    /// Like [`Self::new`], but decodes non-2xx response bodies as `B` instead of the default
+    /// envelope.
+    pub fn new_with_error_body<B: ApiErrorBody + 'static>(
+        api_url: &str,
+        auth: AuthConfig,
+        default_headers: Option<HeaderMap>,
+        token_store: Option<Arc<dyn TokenStore + Send + Sync>>,
+        retry_policy: RetryPolicy,
+    ) -> Result<ApiClient> {
         let base_url = reqwest::Url::parse(api_url)?;
 
         let mut headers = default_headers.unwrap_or_default();
 
-        let oauth_client: Option<oauth2::Client> = match auth {
+        let mut oauth_client: Option<oauth2::Client> = None;
+        let mut oidc_config: Option<OidcConfig> = None;
+        let mut cookie_jar: Option<Arc<reqwest_cookie_store::CookieStoreMutex>> = None;
+
+        match auth {
             AuthConfig::AuthorizationHeader(c) => {
                 let mut auth_value = HeaderValue::from_str(&c.token).expect("Invalid API token value");
                 auth_value.set_sensitive(true);
                 headers.insert(AUTHORIZATION, auth_value);
-                None
             },
             AuthConfig::OAuth2(c) => {
                 let authorize_url = base_url.join(&c.authorize_path)?;
@@ -75,32 +149,65 @@ impl ApiClient {
 
                 // Set the desired scopes
                 c.scopes.iter().for_each(|scope| oauth2_client.add_scope(scope));
-                Some(oauth2_client)
+                oauth_client = Some(oauth2_client);
             },
-            AuthConfig::NoAuth => None,
+            AuthConfig::Oidc(c) => {
+                // Discovery requires a network round-trip, so the oauth2::Client is built lazily
+                // the first time it's needed - see `ApiClient::oauth_client`.
+                oidc_config = Some(c);
+            },
+            AuthConfig::CookieSession => {
+                cookie_jar = Some(Arc::new(reqwest_cookie_store::CookieStoreMutex::new(cookie_store::CookieStore::default())));
+            },
+            AuthConfig::NoAuth => {},
         };
 
-        let client = reqwest::Client::builder().default_headers(headers).build()?;
+        let mut client_builder = reqwest::Client::builder().default_headers(headers);
+        if let Some(jar) = &cookie_jar {
+            client_builder = client_builder.cookie_provider(Arc::clone(jar));
+        }
+        let client = client_builder.build()?;
 
         Ok(ApiClient {
             client,
             base_url,
             oauth_client,
+            oidc_config,
+            oidc_state: tokio::sync::OnceCell::new(),
+            next_rpc_id: AtomicU64::new(0),
+            token_store: token_store.unwrap_or_else(|| Arc::new(InMemoryTokenStore::default())),
+            refresh_lock: tokio::sync::Mutex::new(()),
+            cookie_jar,
+            retry_policy,
+            error_decoder: error_decoder_for::<B>(),
         })
     }
 
-    async fn parse_response<T>(resp: reqwest::Response) -> Result<T>
+    async fn parse_response<T>(&self, resp: reqwest::Response) -> Result<T>
     where
         T: JsonResponse,
     {
+        let status = resp.status();
         let text = resp.text().await?;
         trace!("Raw API Response: {}", text);
+
+        if !status.is_success() {
+            error!("API error response ({}): {}", status, text);
+            return match (self.error_decoder)(status, &text) {
+                Some(err) => Err(err),
+                None => Err(Error::ApiError {
+                    status: status.as_u16(),
+                    code: None,
+                    message: text,
+                    type_: None,
+                    link: None,
+                }),
+            };
+        }
+
         match serde_json::from_str(&text) {
             Ok(r) => {
                 debug!("API Response: {:?}", r);
-
-                // TODO KYC-136 check response code and decode error if present
-
                 Ok(r)
             },
             Err(e) => {
@@ -110,18 +217,15 @@ impl ApiClient {
         }
     }
 
-    async fn handle_request<T>(
+    async fn send_once(
         &self,
         method: Method,
-        path: &str,
+        url: reqwest::Url,
         query: Option<Queries<'_>>,
         data: Option<&serde_json::Value>,
         headers: Option<HeaderMap>,
-    ) -> Result<T>
-    where
-        T: JsonResponse,
-    {
-        let url = self.base_url.join(path)?;
+        token: Option<&StandardToken>,
+    ) -> Result<reqwest::Response> {
         let mut builder = self.client.request(method, url);
 
         if let Some(q) = query {
@@ -133,9 +237,112 @@ impl ApiClient {
         if let Some(h) = headers {
             builder = builder.headers(h);
         }
+        if let Some(t) = token {
+            builder = builder.bearer_auth(t.access_token().secret());
+        }
 
-        let resp = builder.send().await?;
-        ApiClient::parse_response(resp).await
+        builder.send().await.map_err(Error::from)
+    }
+
+    /// Send a single request, transparently attaching the stored OAuth2 token and retrying once
+    /// on a 401 after a refresh. Does not apply `retry_policy` - see `handle_request`.
+    async fn send_with_auth(
+        &self,
+        method: Method,
+        url: reqwest::Url,
+        query: Option<Queries<'_>>,
+        data: Option<&serde_json::Value>,
+        headers: Option<HeaderMap>,
+    ) -> Result<reqwest::Response> {
+        let token = self.token_store.load();
+
+        let resp = self.send_once(method.clone(), url.clone(), query, data, headers.clone(), token.as_ref()).await?;
+
+        if resp.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+
+        // Only worth a transparent retry if we actually had a token to refresh.
+        let refresh_token = match token.as_ref().and_then(|t| t.refresh_token()) {
+            Some(rt) => rt.clone(),
+            None => return Ok(resp),
+        };
+
+        // Guard so concurrent 401s don't each kick off their own refresh.
+        let _guard = self.refresh_lock.lock().await;
+
+        let refreshed = match Self::already_refreshed(token.as_ref(), self.token_store.load()) {
+            Some(current) => current,
+            // `refresh` already persists the new token via `self.token_store.save`.
+            None => self.refresh(&refresh_token).await?,
+        };
+
+        self.send_once(method, url, query, data, headers, Some(&refreshed)).await
+    }
+
+    /// `Some(current)` if `current` already differs from the token loaded before the 401
+    /// (meaning another task refreshed it while we waited on `refresh_lock`), `None` if we
+    /// still need to refresh it ourselves. Split out from [`Self::send_with_auth`] so it's
+    /// unit-testable without a network round-trip.
+    fn already_refreshed(loaded_before: Option<&StandardToken>, current: Option<StandardToken>) -> Option<StandardToken> {
+        match current {
+            Some(current) if loaded_before.map(|t| t.access_token().secret()) != Some(current.access_token().secret()) => Some(current),
+            _ => None,
+        }
+    }
+
+    async fn handle_request<T>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<Queries<'_>>,
+        data: Option<&serde_json::Value>,
+        headers: Option<HeaderMap>,
+    ) -> Result<T>
+    where
+        T: JsonResponse,
+    {
+        let url = self.base_url.join(path)?;
+        let mut attempt = 0u32;
+
+        loop {
+            let is_last_attempt = attempt >= self.retry_policy.max_retries;
+            let outcome = self.send_with_auth(method.clone(), url.clone(), query, data, headers.clone()).await;
+
+            match outcome {
+                Ok(resp) => {
+                    let retryable = retry::is_retryable_status(resp.status());
+                    if retryable && !is_last_attempt {
+                        let delay = retry::retry_after_delay(&resp).unwrap_or_else(|| self.retry_policy.backoff(attempt));
+                        debug!("Retrying {} {} (attempt {}) after {:?}: status {}", method, url, attempt + 1, delay, resp.status());
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+
+                    return match self.parse_response(resp).await {
+                        Err(e) if retryable && attempt > 0 => Err(Error::RetriesExhausted { attempts: attempt + 1, last: Box::new(e) }),
+                        other => other,
+                    };
+                },
+                Err(e) => {
+                    let retryable = retry::is_retryable_error(&e);
+                    if retryable && !is_last_attempt {
+                        let delay = self.retry_policy.backoff(attempt);
+                        debug!("Retrying {} {} (attempt {}) after {:?}: {}", method, url, attempt + 1, delay, e);
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+
+                    return if retryable && attempt > 0 {
+                        Err(Error::RetriesExhausted { attempts: attempt + 1, last: Box::new(e) })
+                    } else {
+                        Err(e)
+                    };
+                },
+            }
+        }
     }
 
     pub async fn get<T>(&self, path: &str, query: Option<Queries<'_>>, headers: Option<HeaderMap>) -> Result<T>
@@ -173,38 +380,326 @@ impl ApiClient {
         self.handle_request(Method::DELETE, path, None, None, headers).await
     }
 
-    // ******
-    // OAuth2
-    // ******
+    // ********
+    // JSON-RPC
+    // ********
 
-    fn ensure_oauth(&self) -> Result<&oauth2::Client> {
-        self.oauth_client.as_ref().ok_or(Error::ClientError("OAuth2 not in use".to_owned()))
+    /// Call a JSON-RPC 2.0 method at `path`, sending `{ "jsonrpc": "2.0", "id", "method", "params" }`
+    /// and decoding the `result` field of the response envelope into `T`.
+    pub async fn json_rpc<P, T>(&self, path: &str, method: &str, params: P) -> Result<T>
+    where
+        P: serde::Serialize,
+        T: JsonResponse,
+    {
+        let id = self.next_rpc_id.fetch_add(1, Ordering::Relaxed);
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let envelope: JsonRpcEnvelope<T> = self.handle_request(Method::POST, path, None, Some(&body), None).await?;
+        Self::decode_json_rpc_envelope(envelope, id)
     }
 
-    pub async fn exchange_code(&self, code: AuthorizationCode) -> Result<StandardToken> {
-        let oauth = self.ensure_oauth()?;
-        oauth
-            .exchange_code(code)
-            .with_client(&self.client)
-            .execute::<StandardToken>()
+    /// Check `envelope.error` before the id: the spec mandates `id: null` on parse-error/
+    /// invalid-request responses, so a real RPC error must not be masked by a spurious
+    /// id-mismatch message. Split out from [`Self::json_rpc`] so it's unit-testable without
+    /// a network round-trip.
+    fn decode_json_rpc_envelope<T>(envelope: JsonRpcEnvelope<T>, expected_id: u64) -> Result<T> {
+        if let Some(err) = envelope.error {
+            return Err(Error::JsonRpcError { code: err.code, message: err.message });
+        }
+
+        if envelope.id.as_ref() != Some(&serde_json::Value::from(expected_id)) {
+            return Err(Error::ClientError(format!(
+                "JSON-RPC response id {:?} does not match request id {}",
+                envelope.id, expected_id
+            )));
+        }
+
+        envelope.result.ok_or_else(|| Error::ClientError("JSON-RPC response missing both result and error".to_owned()))
+    }
+
+    // ***************
+    // OAuth2 / OpenID
+    // ***************
+
+    async fn ensure_oidc(&self) -> Result<&oidc::OidcState> {
+        self.oidc_state
+            .get_or_try_init(|| async {
+                let config = self.oidc_config.as_ref().ok_or_else(|| Error::ClientError("OIDC not in use".to_owned()))?;
+                oidc::OidcState::discover(&self.client, config).await
+            })
             .await
-            .map_err(Error::from)
     }
 
+    async fn oauth_client(&self) -> Result<&oauth2::Client> {
+        if let Some(oauth) = self.oauth_client.as_ref() {
+            return Ok(oauth);
+        }
+        if self.oidc_config.is_some() {
+            return Ok(&self.ensure_oidc().await?.oauth_client);
+        }
+        Err(Error::ClientError("OAuth2 not in use".to_owned()))
+    }
+
+    /// Exchange an authorization code for a token and store it so subsequent requests
+    /// made through this client attach it automatically (see [`Self::send_with_auth`]).
+    pub async fn exchange_code(&self, code: AuthorizationCode) -> Result<StandardToken> {
+        let oauth = self.oauth_client().await?;
+        let token = oauth.exchange_code(code).with_client(&self.client).execute::<StandardToken>().await.map_err(Error::from)?;
+        self.token_store.save(&token);
+        Ok(token)
+    }
+
+    /// Exchange a refresh token for a new token and store it, same as [`Self::exchange_code`].
     pub async fn refresh(&self, refresh_token: &RefreshToken) -> Result<StandardToken> {
-        let oauth = self.ensure_oauth()?;
-        oauth
-            .exchange_refresh_token(refresh_token)
-            .with_client(&self.client)
-            .execute::<StandardToken>()
-            .await
-            .map_err(Error::from)
+        let oauth = self.oauth_client().await?;
+        let token =
+            oauth.exchange_refresh_token(refresh_token).with_client(&self.client).execute::<StandardToken>().await.map_err(Error::from)?;
+        self.token_store.save(&token);
+        Ok(token)
+    }
+
+    /// Manually inject a token into the store, e.g. one loaded from persistent storage at
+    /// startup rather than obtained via [`Self::exchange_code`].
+    pub fn set_token(&self, token: &StandardToken) {
+        self.token_store.save(token);
+    }
+
+    /// Like [`Self::exchange_code`], but for `AuthConfig::Oidc`: also extracts the OIDC
+    /// `id_token` from the token response, if the provider included one, for use with
+    /// [`Self::verify_id_token`]. `StandardToken` itself doesn't expose `id_token`, so this
+    /// decodes the raw response body rather than going through `exchange_code`.
+    pub async fn exchange_code_for_id_token(&self, code: AuthorizationCode) -> Result<(StandardToken, Option<String>)> {
+        let oauth = self.oauth_client().await?;
+        let raw = oauth.exchange_code(code).with_client(&self.client).execute::<serde_json::Value>().await.map_err(Error::from)?;
+
+        let id_token = raw.get("id_token").and_then(|v| v.as_str()).map(str::to_owned);
+        let token: StandardToken = serde_json::from_value(raw)?;
+
+        self.token_store.save(&token);
+        Ok((token, id_token))
+    }
+
+    /// Verify an OIDC `id_token` returned alongside a [`StandardToken`] from
+    /// [`Self::exchange_code_for_id_token`]: check its RS256/ES256 signature against the
+    /// provider's JWKS, and validate `iss`, `aud` and `exp`/`iat`.
+    pub async fn verify_id_token(&self, id_token: &str) -> Result<IdTokenClaims> {
+        let oidc = self.ensure_oidc().await?;
+        oidc.verify_id_token(&self.client, id_token).await
+    }
+
+    // **************
+    // Cookie session
+    // **************
+
+    fn ensure_cookie_session(&self) -> Result<&Arc<reqwest_cookie_store::CookieStoreMutex>> {
+        self.cookie_jar.as_ref().ok_or_else(|| Error::SessionError("cookie session not in use".to_owned()))
+    }
+
+    /// POST `credentials` to `path` and retain the `Set-Cookie` session token from the response
+    /// for subsequent requests. Requires `AuthConfig::CookieSession`.
+    pub async fn login(&self, path: &str, credentials: &serde_json::Value) -> Result<()> {
+        self.ensure_cookie_session()?;
+
+        let url = self.base_url.join(path)?;
+        let resp = self.client.post(url).json(credentials).send().await?;
+        Self::check_login_response(resp).await
+    }
+
+    /// Split out from [`Self::login`] so the failure-message formatting is unit-testable
+    /// without a network round-trip.
+    async fn check_login_response(resp: reqwest::Response) -> Result<()> {
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(Error::SessionError(format!("login failed with status {}: {}", status, text)));
+        }
+
+        Ok(())
+    }
+
+    /// Drop the current session cookies.
+    pub fn logout(&self) -> Result<()> {
+        let jar = self.ensure_cookie_session()?;
+        jar.lock().unwrap().clear();
+        Ok(())
+    }
+
+    /// Export the current session cookies so they can be persisted across process restarts.
+    pub fn export_session(&self) -> Result<Vec<(String, String)>> {
+        let jar = self.ensure_cookie_session()?;
+        let store = jar.lock().unwrap();
+        Ok(store.iter_any().map(|c| (c.name().to_owned(), c.value().to_owned())).collect())
+    }
+
+    /// Restore session cookies previously returned by [`Self::export_session`].
+    pub fn import_session(&self, cookies: Vec<(String, String)>) -> Result<()> {
+        let jar = self.ensure_cookie_session()?;
+        let domain = self.base_url.host_str().unwrap_or_default();
+        let mut store = jar.lock().unwrap();
+
+        for (name, value) in cookies {
+            let raw = format!("{}={}; Domain={}; Path=/", name, value, domain);
+            let cookie = cookie::Cookie::parse(raw).map_err(|e| Error::SessionError(e.to_string()))?;
+            store
+                .insert_raw(&cookie, &self.base_url)
+                .map_err(|e| Error::SessionError(e.to_string()))?;
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    //use super::*;
+    use super::*;
+
+    // ********
+    // JSON-RPC
+    // ********
+
+    fn envelope(
+        id: Option<serde_json::Value>,
+        result: Option<serde_json::Value>,
+        error: Option<JsonRpcErrorBody>,
+    ) -> JsonRpcEnvelope<serde_json::Value> {
+        JsonRpcEnvelope { id, result, error }
+    }
+
+    #[test]
+    fn json_rpc_decodes_result() {
+        let env = envelope(Some(serde_json::json!(1)), Some(serde_json::json!({"ok": true})), None);
+        let result = ApiClient::decode_json_rpc_envelope(env, 1).unwrap();
+        assert_eq!(result, serde_json::json!({"ok": true}));
+    }
+
+    #[test]
+    fn json_rpc_surfaces_error() {
+        let env = envelope(Some(serde_json::json!(1)), None, Some(JsonRpcErrorBody { code: -32601, message: "method not found".to_owned() }));
+        let err = ApiClient::decode_json_rpc_envelope(env, 1).unwrap_err();
+        match err {
+            Error::JsonRpcError { code, message } => {
+                assert_eq!(code, -32601);
+                assert_eq!(message, "method not found");
+            },
+            other => panic!("expected JsonRpcError, got {other:?}"),
+        }
+    }
 
-    // put unittests here
+    #[test]
+    fn json_rpc_rejects_id_mismatch() {
+        let env = envelope(Some(serde_json::json!(2)), Some(serde_json::json!({"ok": true})), None);
+        let err = ApiClient::decode_json_rpc_envelope(env, 1).unwrap_err();
+        assert!(matches!(err, Error::ClientError(_)));
+    }
+
+    #[test]
+    fn json_rpc_null_id_with_error_surfaces_the_error_not_a_mismatch() {
+        let env = envelope(None, None, Some(JsonRpcErrorBody { code: -32700, message: "parse error".to_owned() }));
+        let err = ApiClient::decode_json_rpc_envelope(env, 1).unwrap_err();
+        match err {
+            Error::JsonRpcError { code, .. } => assert_eq!(code, -32700),
+            other => panic!("expected JsonRpcError, got {other:?}"),
+        }
+    }
+
+    // *****************
+    // Token store / auth
+    // *****************
+
+    fn token(access_token: &str) -> StandardToken {
+        serde_json::from_value(serde_json::json!({ "access_token": access_token, "token_type": "Bearer" })).unwrap()
+    }
+
+    #[test]
+    fn in_memory_token_store_round_trips() {
+        let store = InMemoryTokenStore::default();
+        assert!(store.load().is_none());
+
+        let t = token("access-1");
+        store.save(&t);
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.access_token().secret(), t.access_token().secret());
+    }
+
+    #[test]
+    fn already_refreshed_detects_concurrent_refresh() {
+        let before = token("stale");
+        let current = token("fresh");
+
+        let picked = ApiClient::already_refreshed(Some(&before), Some(current.clone()));
+        assert_eq!(picked.unwrap().access_token().secret(), current.access_token().secret());
+    }
+
+    #[test]
+    fn already_refreshed_is_none_when_token_unchanged() {
+        let before = token("same");
+        let current = token("same");
+
+        assert!(ApiClient::already_refreshed(Some(&before), Some(current)).is_none());
+    }
+
+    #[test]
+    fn already_refreshed_is_none_when_nothing_loaded_yet() {
+        assert!(ApiClient::already_refreshed(None, None).is_none());
+    }
+
+    // **************
+    // Cookie session
+    // **************
+
+    fn cookie_client() -> ApiClient {
+        ApiClient::new("http://localhost/", AuthConfig::CookieSession, None, None, RetryPolicy::none()).unwrap()
+    }
+
+    #[test]
+    fn export_import_session_round_trips() {
+        let client = cookie_client();
+        client.import_session(vec![("session".to_owned(), "abc123".to_owned())]).unwrap();
+
+        let exported = client.export_session().unwrap();
+        assert_eq!(exported, vec![("session".to_owned(), "abc123".to_owned())]);
+
+        let other = cookie_client();
+        other.import_session(exported).unwrap();
+        assert_eq!(other.export_session().unwrap(), vec![("session".to_owned(), "abc123".to_owned())]);
+    }
+
+    #[test]
+    fn logout_clears_session_cookies() {
+        let client = cookie_client();
+        client.import_session(vec![("session".to_owned(), "abc123".to_owned())]).unwrap();
+        assert!(!client.export_session().unwrap().is_empty());
+
+        client.logout().unwrap();
+        assert!(client.export_session().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn login_failure_surfaces_response_body() {
+        let raw = http::Response::builder().status(401).body("invalid credentials".as_bytes().to_vec()).unwrap();
+        let resp: reqwest::Response = raw.into();
+
+        let err = ApiClient::check_login_response(resp).await.unwrap_err();
+        match err {
+            Error::SessionError(msg) => {
+                assert!(msg.contains("401"));
+                assert!(msg.contains("invalid credentials"));
+            },
+            other => panic!("expected SessionError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn login_success_is_ok() {
+        let raw = http::Response::builder().status(200).body(Vec::new()).unwrap();
+        let resp: reqwest::Response = raw.into();
+
+        assert!(ApiClient::check_login_response(resp).await.is_ok());
+    }
 }
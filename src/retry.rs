@@ -0,0 +1,122 @@
+//! Retry subsystem for `ApiClient::handle_request`: full-jitter exponential backoff for
+//! transient failures, honoring `Retry-After` on 429s.
+
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+}
+
+impl RetryPolicy {
+    /// No retries - the behavior of `ApiClient` before this policy existed, and the default.
+    pub fn none() -> Self {
+        RetryPolicy { max_retries: 0, base_delay: Duration::ZERO, max_delay: Duration::ZERO, jitter: false }
+    }
+
+    /// On attempt `n`, sleep a random duration in `[0, min(max_delay, base_delay * 2^n)]` when
+    /// `jitter` is set, or exactly `min(max_delay, base_delay * 2^n)` when it is not.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt);
+        let exp_millis = self.base_delay.as_millis().saturating_mul(factor as u128);
+        let capped_millis = exp_millis.min(self.max_delay.as_millis()) as u64;
+
+        if self.jitter {
+            Duration::from_millis((rand::random::<f64>() * capped_millis as f64) as u64)
+        } else {
+            Duration::from_millis(capped_millis)
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::none()
+    }
+}
+
+pub(crate) fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 502 | 503 | 504)
+}
+
+pub(crate) fn is_retryable_error(err: &crate::error::Error) -> bool {
+    match err {
+        crate::error::Error::ReqwestError(e) => e.is_connect() || e.is_timeout(),
+        _ => false,
+    }
+}
+
+/// Honor a `Retry-After: <seconds>` header on a 429 response instead of the computed backoff.
+pub(crate) fn retry_after_delay(resp: &reqwest::Response) -> Option<Duration> {
+    if resp.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, retry_after: Option<&str>) -> reqwest::Response {
+        let mut builder = http::Response::builder().status(status);
+        if let Some(v) = retry_after {
+            builder = builder.header(reqwest::header::RETRY_AFTER, v);
+        }
+        builder.body(Vec::new()).unwrap().into()
+    }
+
+    #[test]
+    fn backoff_caps_at_max_delay() {
+        let policy = RetryPolicy { max_retries: 10, base_delay: Duration::from_millis(100), max_delay: Duration::from_secs(1), jitter: false };
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        // base_delay * 2^4 = 1600ms, clamped to max_delay.
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        // Large attempts must not overflow and should still clamp.
+        assert_eq!(policy.backoff(63), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn backoff_with_jitter_stays_within_bounds() {
+        let policy = RetryPolicy { max_retries: 10, base_delay: Duration::from_millis(100), max_delay: Duration::from_secs(1), jitter: true };
+
+        for attempt in 0..6 {
+            let delay = policy.backoff(attempt);
+            assert!(delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        for status in [429, 502, 503, 504] {
+            assert!(is_retryable_status(reqwest::StatusCode::from_u16(status).unwrap()));
+        }
+        for status in [200, 400, 401, 404, 500] {
+            assert!(!is_retryable_status(reqwest::StatusCode::from_u16(status).unwrap()));
+        }
+    }
+
+    #[test]
+    fn retry_after_honored_on_429() {
+        let resp = response(429, Some("7"));
+        assert_eq!(retry_after_delay(&resp), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn retry_after_ignored_when_missing_or_not_429() {
+        assert_eq!(retry_after_delay(&response(429, None)), None);
+        assert_eq!(retry_after_delay(&response(503, Some("7"))), None);
+        assert_eq!(retry_after_delay(&response(429, Some("not-a-number"))), None);
+    }
+}
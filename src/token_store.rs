@@ -0,0 +1,27 @@
+//! Pluggable storage for the OAuth2 token `ApiClient` uses to authenticate requests.
+
+use std::sync::Mutex;
+
+use crate::StandardToken;
+
+/// Backs `ApiClient`'s automatic token refresh. The default is [`InMemoryTokenStore`];
+/// implement this trait to persist tokens to a file, database, etc.
+pub trait TokenStore {
+    fn load(&self) -> Option<StandardToken>;
+    fn save(&self, token: &StandardToken);
+}
+
+#[derive(Default)]
+pub struct InMemoryTokenStore {
+    token: Mutex<Option<StandardToken>>,
+}
+
+impl TokenStore for InMemoryTokenStore {
+    fn load(&self) -> Option<StandardToken> {
+        self.token.lock().unwrap().clone()
+    }
+
+    fn save(&self, token: &StandardToken) {
+        *self.token.lock().unwrap() = Some(token.clone());
+    }
+}
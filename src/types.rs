@@ -13,10 +13,125 @@ pub type Date = time::Date;
 /// #[serde(with = "time::serde::timestamp::option")]
 pub type DateTime = time::OffsetDateTime;
 
+/// Some JSON APIs pack a date into a single integer, e.g. `20221214` for 2022-12-14, instead of
+/// an ISO8601 string. Use with `#[serde(with = "types::compact_date")]` (or `::option` for
+/// `Option<Date>` fields).
+pub mod compact_date {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use time::Date;
+
+    pub fn serialize<S>(date: &Date, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let packed = date.year() as u32 * 10000 + date.month() as u32 * 100 + date.day() as u32;
+        serializer.serialize_u32(packed)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Date, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let packed = u32::deserialize(deserializer)?;
+        let year = (packed / 10000) as i32;
+        let month = (packed % 10000) / 100;
+        let day = packed % 100;
+
+        let month = time::Month::try_from(month as u8).map_err(serde::de::Error::custom)?;
+        Date::from_calendar_date(year, month, day as u8).map_err(serde::de::Error::custom)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+        use time::Date;
+
+        pub fn serialize<S>(date: &Option<Date>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match date {
+                Some(date) => super::serialize(date, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Date>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match Option::<u32>::deserialize(deserializer)? {
+                Some(packed) => {
+                    let year = (packed / 10000) as i32;
+                    let month = (packed % 10000) / 100;
+                    let day = packed % 100;
+
+                    let month = time::Month::try_from(month as u8).map_err(serde::de::Error::custom)?;
+                    Ok(Some(Date::from_calendar_date(year, month, day as u8).map_err(serde::de::Error::custom)?))
+                },
+                None => Ok(None),
+            }
+        }
+    }
+}
+
+/// Some JSON APIs pack a time into a single integer, e.g. `1345` for 13:45, instead of an ISO8601
+/// string. Use with `#[serde(with = "types::compact_time")]` (or `::option` for `Option<Time>` fields).
+pub mod compact_time {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use time::Time;
+
+    pub fn serialize<S>(time: &Time, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let packed = time.hour() as u32 * 100 + time.minute() as u32;
+        serializer.serialize_u32(packed)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Time, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let packed = u32::deserialize(deserializer)?;
+        let hour = (packed / 100) as u8;
+        let minute = (packed % 100) as u8;
+        Time::from_hms(hour, minute, 0).map_err(serde::de::Error::custom)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+        use time::Time;
+
+        pub fn serialize<S>(time: &Option<Time>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match time {
+                Some(time) => super::serialize(time, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Time>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match Option::<u32>::deserialize(deserializer)? {
+                Some(packed) => {
+                    let hour = (packed / 100) as u8;
+                    let minute = (packed % 100) as u8;
+                    Ok(Some(Time::from_hms(hour, minute, 0).map_err(serde::de::Error::custom)?))
+                },
+                None => Ok(None),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
     use serde_json::json;
 
     #[test]
@@ -118,4 +233,84 @@ mod tests {
         let got2: ContainsOptionalTimestamp = serde_json::from_value(json!({ "datetime": null })).unwrap();
         assert_eq!(got2, ContainsOptionalTimestamp { datetime: None });
     }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+    struct ContainsCompactDate {
+        #[serde(with = "compact_date")]
+        pub date: Date,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+    struct ContainsOptionalCompactDate {
+        #[serde(with = "compact_date::option")]
+        pub date: Option<Date>,
+    }
+
+    #[test]
+    fn test_compact_date() {
+        let got: ContainsCompactDate = serde_json::from_value(json!({ "date": 20221214 })).unwrap();
+        let expected_date = Date::from_calendar_date(2022, time::Month::December, 14).unwrap();
+        let expected = ContainsCompactDate { date: expected_date };
+        assert_eq!(got, expected);
+
+        let round_tripped = serde_json::to_value(&expected).unwrap();
+        assert_eq!(round_tripped, json!({ "date": 20221214 }));
+    }
+
+    #[test]
+    fn test_compact_date_option() {
+        let got: ContainsOptionalCompactDate = serde_json::from_value(json!({ "date": 20221214 })).unwrap();
+        let expected_date = Date::from_calendar_date(2022, time::Month::December, 14).unwrap();
+        let expected = ContainsOptionalCompactDate { date: Some(expected_date) };
+        assert_eq!(got, expected);
+
+        let round_tripped = serde_json::to_value(&expected).unwrap();
+        assert_eq!(round_tripped, json!({ "date": 20221214 }));
+
+        let got2: ContainsOptionalCompactDate = serde_json::from_value(json!({ "date": null })).unwrap();
+        assert_eq!(got2, ContainsOptionalCompactDate { date: None });
+    }
+
+    #[test]
+    fn test_compact_date_invalid() {
+        let result: std::result::Result<ContainsCompactDate, _> = serde_json::from_value(json!({ "date": 20221399 }));
+        assert!(result.is_err());
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+    struct ContainsCompactTime {
+        #[serde(with = "compact_time")]
+        pub time: time::Time,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+    struct ContainsOptionalCompactTime {
+        #[serde(with = "compact_time::option")]
+        pub time: Option<time::Time>,
+    }
+
+    #[test]
+    fn test_compact_time() {
+        let got: ContainsCompactTime = serde_json::from_value(json!({ "time": 1345 })).unwrap();
+        let expected_time = time::Time::from_hms(13, 45, 0).unwrap();
+        let expected = ContainsCompactTime { time: expected_time };
+        assert_eq!(got, expected);
+
+        let round_tripped = serde_json::to_value(&expected).unwrap();
+        assert_eq!(round_tripped, json!({ "time": 1345 }));
+    }
+
+    #[test]
+    fn test_compact_time_option() {
+        let got: ContainsOptionalCompactTime = serde_json::from_value(json!({ "time": 1345 })).unwrap();
+        let expected_time = time::Time::from_hms(13, 45, 0).unwrap();
+        let expected = ContainsOptionalCompactTime { time: Some(expected_time) };
+        assert_eq!(got, expected);
+
+        let round_tripped = serde_json::to_value(&expected).unwrap();
+        assert_eq!(round_tripped, json!({ "time": 1345 }));
+
+        let got2: ContainsOptionalCompactTime = serde_json::from_value(json!({ "time": null })).unwrap();
+        assert_eq!(got2, ContainsOptionalCompactTime { time: None });
+    }
 }